@@ -1,10 +1,197 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::thread;
 use std::time::Duration;
 
-fn read_uptime() -> u64 {
-    fs::read_to_string("/proc/uptime")
-        .unwrap_or_default()
+use rustix::fs::{Mode, OFlags};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+struct Metrics {
+    hostname: String,
+    uptime: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mem_total_kb: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mem_free_kb: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    load_1m: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cpu_usage: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filesystems: Option<Vec<Filesystem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperatures: Option<BTreeMap<String, f64>>,
+}
+
+/// Which collectors run each cycle and how often the loop samples. Loaded from
+/// an optional TOML file (`AVOCADO_METRICS_CONFIG`) with env-var overrides.
+#[derive(Deserialize)]
+#[serde(default)]
+struct Config {
+    interval_secs: u64,
+    memory: bool,
+    load: bool,
+    cpu: bool,
+    temps: bool,
+    filesystems: bool,
+    format: OutputFormat,
+}
+
+/// How each sample is rendered to stdout.
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum OutputFormat {
+    Json,
+    Text,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            interval_secs: 30,
+            memory: true,
+            load: true,
+            cpu: true,
+            temps: true,
+            filesystems: true,
+            format: OutputFormat::Json,
+        }
+    }
+}
+
+impl Config {
+    /// TOML file first (if `AVOCADO_METRICS_CONFIG` points at one), then env
+    /// overrides on top.
+    fn load() -> Config {
+        let mut config = std::env::var("AVOCADO_METRICS_CONFIG")
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|raw| toml::from_str(&raw).ok())
+            .unwrap_or_default();
+
+        if let Some(secs) = std::env::var("AVOCADO_METRICS_INTERVAL")
+            .ok()
+            .and_then(|v| v.parse().ok())
+        {
+            config.interval_secs = secs;
+        }
+
+        if let Ok(format) = std::env::var("AVOCADO_METRICS_FORMAT") {
+            match format.as_str() {
+                "json" => config.format = OutputFormat::Json,
+                "text" => config.format = OutputFormat::Text,
+                _ => {}
+            }
+        }
+
+        config
+    }
+}
+
+/// A parsed `/proc/mounts` record joined with its `statvfs` usage figures.
+#[derive(Serialize)]
+struct Filesystem {
+    volume: String,
+    mount_point: String,
+    fstype: String,
+    total_bytes: u64,
+    free_bytes: u64,
+    used_bytes: u64,
+}
+
+/// Aggregate CPU time counters from the `cpu` line of `/proc/stat`.
+#[derive(Clone, Copy)]
+struct CpuTimes {
+    user: u64,
+    nice: u64,
+    system: u64,
+    idle: u64,
+    iowait: u64,
+    irq: u64,
+    softirq: u64,
+    steal: u64,
+}
+
+impl CpuTimes {
+    fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+
+    fn idle_all(&self) -> u64 {
+        self.idle + self.iowait
+    }
+}
+
+/// Reads small `/proc` files into a single reusable buffer so the steady-state
+/// loop does not allocate a fresh `String` every cycle.
+struct ProcReader {
+    buf: Vec<u8>,
+}
+
+impl ProcReader {
+    fn new() -> Self {
+        ProcReader {
+            buf: Vec::with_capacity(4096),
+        }
+    }
+
+    /// Read `path` fully into the shared buffer and return it as UTF-8. On any
+    /// I/O error the buffer is left empty and an empty string is returned.
+    fn slurp(&mut self, path: &str) -> &str {
+        self.buf.clear();
+        if let Ok(fd) = rustix::fs::open(path, OFlags::RDONLY, Mode::empty()) {
+            let mut chunk = [0u8; 4096];
+            loop {
+                match rustix::io::read(&fd, &mut chunk) {
+                    Ok(0) => break,
+                    Ok(n) => self.buf.extend_from_slice(&chunk[..n]),
+                    Err(_) => {
+                        self.buf.clear();
+                        break;
+                    }
+                }
+            }
+        }
+        std::str::from_utf8(&self.buf).unwrap_or("")
+    }
+}
+
+fn parse_cpu_times(stat: &str) -> Option<CpuTimes> {
+    let line = stat.lines().find(|line| line.starts_with("cpu "))?;
+    let mut fields = line.split_whitespace().skip(1).map(|s| s.parse().ok());
+    Some(CpuTimes {
+        user: fields.next().flatten().unwrap_or(0),
+        nice: fields.next().flatten().unwrap_or(0),
+        system: fields.next().flatten().unwrap_or(0),
+        idle: fields.next().flatten().unwrap_or(0),
+        iowait: fields.next().flatten().unwrap_or(0),
+        irq: fields.next().flatten().unwrap_or(0),
+        softirq: fields.next().flatten().unwrap_or(0),
+        steal: fields.next().flatten().unwrap_or(0),
+    })
+}
+
+/// Busy percentage (0.0..=100.0) between two `/proc/stat` samples, or `None` if
+/// the total delta is zero (identical samples).
+fn cpu_usage(prev: &CpuTimes, now: &CpuTimes) -> Option<f64> {
+    let total_delta = now.total().saturating_sub(prev.total());
+    if total_delta == 0 {
+        return None;
+    }
+    let idle_delta = now.idle_all().saturating_sub(prev.idle_all());
+    Some((1.0 - idle_delta as f64 / total_delta as f64) * 100.0)
+}
+
+fn parse_uptime(uptime: &str) -> u64 {
+    uptime
         .split_whitespace()
         .next()
         .and_then(|s| s.parse::<f64>().ok())
@@ -12,9 +199,8 @@ fn read_uptime() -> u64 {
         .unwrap_or(0)
 }
 
-fn read_meminfo(key: &str) -> u64 {
-    fs::read_to_string("/proc/meminfo")
-        .unwrap_or_default()
+fn parse_meminfo(meminfo: &str, key: &str) -> u64 {
+    meminfo
         .lines()
         .find(|line| line.starts_with(key))
         .and_then(|line| line.split_whitespace().nth(1))
@@ -22,15 +208,162 @@ fn read_meminfo(key: &str) -> u64 {
         .unwrap_or(0)
 }
 
-fn read_loadavg() -> String {
-    fs::read_to_string("/proc/loadavg")
-        .unwrap_or_default()
+fn parse_loadavg(loadavg: &str) -> String {
+    loadavg
         .split_whitespace()
         .next()
         .unwrap_or("0.00")
         .to_string()
 }
 
+/// Filesystem types worth reporting; pseudo-filesystems (proc, sysfs, cgroup,
+/// tmpfs, …) are skipped since they do not represent backing storage.
+fn is_real_fstype(fstype: &str) -> bool {
+    matches!(
+        fstype,
+        "ext2"
+            | "ext3"
+            | "ext4"
+            | "xfs"
+            | "btrfs"
+            | "f2fs"
+            | "vfat"
+            | "exfat"
+            | "ntfs"
+            | "squashfs"
+            | "overlay"
+    )
+}
+
+fn read_filesystems() -> Vec<Filesystem> {
+    fs::read_to_string("/proc/mounts")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let volume = fields.next()?;
+            let mount_point = fields.next()?;
+            let fstype = fields.next()?;
+            // Require at least the options field so malformed lines are dropped.
+            fields.next()?;
+            if !is_real_fstype(fstype) {
+                return None;
+            }
+
+            let stat = rustix::fs::statvfs(mount_point).ok()?;
+            let frsize = stat.f_frsize as u64;
+            let total = stat.f_blocks as u64 * frsize;
+            let free = stat.f_bfree as u64 * frsize;
+            Some(Filesystem {
+                volume: volume.to_string(),
+                mount_point: mount_point.to_string(),
+                fstype: fstype.to_string(),
+                total_bytes: total,
+                free_bytes: free,
+                used_bytes: total.saturating_sub(free),
+            })
+        })
+        .collect()
+}
+
+/// Read each `/sys/class/thermal/thermal_zone*/temp` (millidegrees Celsius),
+/// keyed by the zone's `type`. Zones that cannot be read are omitted.
+fn read_temps() -> BTreeMap<String, f64> {
+    let mut temps = BTreeMap::new();
+    let entries = match fs::read_dir("/sys/class/thermal") {
+        Ok(entries) => entries,
+        Err(_) => return temps,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        if !name.to_string_lossy().starts_with("thermal_zone") {
+            continue;
+        }
+
+        let millidegrees = match fs::read_to_string(path.join("temp")) {
+            Ok(raw) => match raw.trim().parse::<f64>() {
+                Ok(value) => value,
+                Err(_) => continue,
+            },
+            Err(_) => continue,
+        };
+        let zone_type = fs::read_to_string(path.join("type"))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|_| name.to_string_lossy().into_owned());
+
+        temps.insert(zone_type, millidegrees / 1000.0);
+    }
+
+    temps
+}
+
+/// Format a kibibyte count as the largest sensible binary unit (KiB/MiB/GiB/TiB).
+fn format_kib(kib: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    let mut value = kib as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Decompose raw uptime seconds into `"N days N hours N minutes"`, suppressing
+/// leading zero units.
+fn format_uptime(secs: u64) -> String {
+    let days = secs / 86_400;
+    let rem = secs % 86_400;
+    let hours = rem / 3_600;
+    let minutes = (rem % 3_600) / 60;
+
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{} days", days));
+    }
+    if hours > 0 || !parts.is_empty() {
+        parts.push(format!("{} hours", hours));
+    }
+    parts.push(format!("{} minutes", minutes));
+    parts.join(" ")
+}
+
+/// Render a sample as a glanceable multi-line console view.
+fn render_text(m: &Metrics) -> String {
+    let mut out = format!("{} — up {}\n", m.hostname, format_uptime(m.uptime));
+    if let (Some(total), Some(free)) = (m.mem_total_kb, m.mem_free_kb) {
+        out.push_str(&format!(
+            "  memory: {} free / {}\n",
+            format_kib(free),
+            format_kib(total)
+        ));
+    }
+    if let Some(load) = &m.load_1m {
+        out.push_str(&format!("  load (1m): {}\n", load));
+    }
+    if let Some(usage) = m.cpu_usage {
+        out.push_str(&format!("  cpu: {:.1}%\n", usage));
+    }
+    if let Some(temps) = &m.temperatures {
+        for (zone, celsius) in temps {
+            out.push_str(&format!("  temp[{}]: {:.1}°C\n", zone, celsius));
+        }
+    }
+    if let Some(filesystems) = &m.filesystems {
+        for fs in filesystems {
+            out.push_str(&format!(
+                "  fs[{}]: {} used / {}\n",
+                fs.mount_point,
+                format_kib(fs.used_bytes / 1024),
+                format_kib(fs.total_bytes / 1024)
+            ));
+        }
+    }
+    out
+}
+
 fn read_hostname() -> String {
     fs::read_to_string("/etc/hostname")
         .unwrap_or_else(|_| "unknown".to_string())
@@ -39,19 +372,57 @@ fn read_hostname() -> String {
 }
 
 fn main() {
-    let interval = Duration::from_secs(30);
+    let config = Config::load();
+    let interval = Duration::from_secs(config.interval_secs);
     let hostname = read_hostname();
+    let mut reader = ProcReader::new();
+    let mut prev_cpu: Option<CpuTimes> = None;
 
     loop {
-        let uptime = read_uptime();
-        let mem_total_kb = read_meminfo("MemTotal:");
-        let mem_free_kb = read_meminfo("MemFree:");
-        let load_1m = read_loadavg();
-
-        println!(
-            r#"{{"hostname":"{}","uptime":{},"mem_total_kb":{},"mem_free_kb":{},"load_1m":"{}"}}"#,
-            hostname, uptime, mem_total_kb, mem_free_kb, load_1m
-        );
+        let cpu_usage = if config.cpu {
+            let cpu_now = parse_cpu_times(reader.slurp("/proc/stat"));
+            let usage = match (prev_cpu, cpu_now) {
+                (Some(prev), Some(now)) => cpu_usage(&prev, &now),
+                _ => None,
+            };
+            prev_cpu = cpu_now;
+            usage
+        } else {
+            None
+        };
+
+        let uptime = parse_uptime(reader.slurp("/proc/uptime"));
+        let (mem_total_kb, mem_free_kb) = if config.memory {
+            let meminfo = reader.slurp("/proc/meminfo");
+            (
+                Some(parse_meminfo(meminfo, "MemTotal:")),
+                Some(parse_meminfo(meminfo, "MemFree:")),
+            )
+        } else {
+            (None, None)
+        };
+        let load_1m = config
+            .load
+            .then(|| parse_loadavg(reader.slurp("/proc/loadavg")));
+
+        let metrics = Metrics {
+            hostname: hostname.clone(),
+            uptime,
+            mem_total_kb,
+            mem_free_kb,
+            load_1m,
+            cpu_usage,
+            filesystems: config.filesystems.then(read_filesystems),
+            temperatures: config.temps.then(read_temps),
+        };
+
+        match config.format {
+            OutputFormat::Json => match serde_json::to_string(&metrics) {
+                Ok(line) => println!("{}", line),
+                Err(e) => eprintln!("failed to serialize metrics: {}", e),
+            },
+            OutputFormat::Text => print!("{}", render_text(&metrics)),
+        }
 
         thread::sleep(interval);
     }